@@ -0,0 +1,273 @@
+/**
+ * Folding several `A * S = T` instances that share the same public matrix
+ * `a` into a single instance, via the standard random-linear-combination
+ * soundness argument.
+ *
+ * Given `m` instances `A * S_k = T_k` (common when proving a batch of
+ * ciphertexts against the same key), a verifier-derived Fiat-Shamir
+ * challenge `gamma` lets a prover fold them into one relation: `S' = sum_k
+ * gamma^k * S_k` and `T' = sum_k gamma^k * T_k`, so a single proof of `A *
+ * S' = T'` attests to all `m` original relations. Each `T_k` is absorbed
+ * into the transcript before `gamma` is squeezed, so the challenge can't
+ * be chosen to cancel out a cheating prover's error term. Each instance's
+ * bound `B_k` is folded the same way as `S_k` (scaled by `gamma^k` before
+ * being summed in), since a folded term `gamma^k * S_k` can have a far
+ * larger bound than `S_k` alone; folding fails with
+ * [`BatchError::BoundsOverflow`] if tracking that growth overflows the
+ * `u128` scale factor, or if the folded bound itself no longer fits
+ * `Bounds`'s own representation.
+ */
+use merlin::Transcript;
+use sunscreen_math::{poly::Polynomial, ring::Ring};
+
+use crate::{
+    linear_algebra::Matrix,
+    secret_witness::{MlockError, SecretPayload, SecretWitness},
+    test::LatticeProblem,
+    Bounds,
+};
+
+/// An error folding a batch of lattice problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchError {
+    /// No instances were supplied to fold.
+    Empty,
+    /// Two instances didn't share the same public matrix `a`.
+    MismatchedPublicMatrix,
+    /// Two instances didn't share the same divisor `f`.
+    MismatchedDivisor,
+    /// Folding the per-instance bounds by `gamma_pow` would overflow
+    /// either the `u128` scale factor used to track bound growth, or the
+    /// folded `Matrix<Bounds>` itself.
+    BoundsOverflow,
+    /// Locking the folded witness `S'` in memory failed.
+    Lock(MlockError),
+    /// An instance's `s` didn't hold exactly the one secret matrix
+    /// [`LatticeProblem`] expects it to.
+    MalformedWitness,
+}
+
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "at least one instance is required to form a batch"),
+            Self::MismatchedPublicMatrix => {
+                write!(f, "all instances in a batch must share the same public matrix `a`")
+            }
+            Self::MismatchedDivisor => {
+                write!(f, "all instances in a batch must share the same divisor `f`")
+            }
+            Self::BoundsOverflow => write!(
+                f,
+                "folding the batch's bounds by gamma^k overflowed the bound-tracking scale \
+                 factor, or no longer fits the bound type"
+            ),
+            Self::Lock(err) => write!(f, "failed to lock the folded witness: {}", err),
+            Self::MalformedWitness => {
+                write!(f, "an instance's witness did not hold exactly one secret matrix")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/**
+ * Fold `instances`, which must all share the same public matrix `a` and
+ * divisor `f`, into a single [`LatticeProblem`] via a Fiat-Shamir-derived
+ * random linear combination.
+ *
+ * # Remarks
+ * This is the first caller to scale a `Matrix<Bounds>` by a `u128`
+ * growth factor or compare `Bounds` values; it relies on
+ * `Matrix<Bounds>: Add<Output = Matrix<Bounds>> + Mul<u128, Output =
+ * Matrix<Bounds>>` and `Bounds: PartialOrd` already existing on those
+ * types (defined alongside `Bounds` itself, outside this module) -
+ * add them there if they don't yet exist.
+ */
+pub fn batch<Q>(instances: &[LatticeProblem<Q>]) -> Result<LatticeProblem<Q>, BatchError>
+where
+    Q: Ring + Clone + PartialEq + From<u64> + Into<u64>,
+    Matrix<Polynomial<Q>>: Clone
+        + PartialEq
+        + SecretPayload
+        + std::ops::Add<Output = Matrix<Polynomial<Q>>>
+        + std::ops::Mul<Q, Output = Matrix<Polynomial<Q>>>,
+    Polynomial<Q>: Clone + PartialEq,
+    Matrix<Bounds>: Clone
+        + std::ops::Add<Output = Matrix<Bounds>>
+        + std::ops::Mul<u128, Output = Matrix<Bounds>>,
+    Bounds: PartialOrd,
+{
+    let (first, rest) = instances
+        .split_first()
+        .ok_or(BatchError::Empty)?;
+
+    for instance in rest {
+        if instance.a != first.a {
+            return Err(BatchError::MismatchedPublicMatrix);
+        }
+        if instance.f != first.f {
+            return Err(BatchError::MismatchedDivisor);
+        }
+    }
+
+    let mut transcript = Transcript::new(b"sunscreen-logproof-batch");
+    for instance in instances {
+        transcript.append_message(b"t_k", &matrix_to_bytes(&instance.t));
+    }
+
+    let gamma = squeeze_challenge::<Q>(&mut transcript);
+
+    let mut s_prime = first.s.as_single().ok_or(BatchError::MalformedWitness)?.clone();
+    let mut t_prime = first.t.clone();
+    let mut b_prime = first.b.clone();
+    let mut gamma_pow = Q::from(1u64);
+    let mut gamma_pow_scale = 1u128;
+    let gamma_scale = gamma.clone().into() as u128;
+
+    for instance in rest {
+        gamma_pow = gamma_pow * gamma.clone();
+        gamma_pow_scale = fold_bound_scale(gamma_pow_scale, gamma_scale)?;
+
+        s_prime = s_prime
+            + instance.s.as_single().ok_or(BatchError::MalformedWitness)?.clone() * gamma_pow.clone();
+        t_prime = t_prime + instance.t.clone() * gamma_pow.clone();
+
+        let folded_b = b_prime.clone() + instance.b.clone() * gamma_pow_scale;
+        if !folded_bounds_fit(&b_prime, &folded_b) {
+            return Err(BatchError::BoundsOverflow);
+        }
+        b_prime = folded_b;
+    }
+
+    let s = SecretWitness::new_single(s_prime).map_err(BatchError::Lock)?;
+
+    Ok(LatticeProblem {
+        a: first.a.clone(),
+        s,
+        t: t_prime,
+        f: first.f.clone(),
+        b: b_prime,
+    })
+}
+
+/// Scale `bound_scale` (the running `gamma^k` tracked outside `Q`, see the
+/// module doc) by another factor of `gamma_scale`, the way [`batch`] folds
+/// a bound forward by one instance. Pulled out on its own so the
+/// overflow path - the exact thing the bound-tracking scale factor exists
+/// to catch - can be tested without needing a whole [`LatticeProblem`].
+fn fold_bound_scale(bound_scale: u128, gamma_scale: u128) -> Result<u128, BatchError> {
+    bound_scale.checked_mul(gamma_scale).ok_or(BatchError::BoundsOverflow)
+}
+
+/// Whether folding `before` forward by one instance into `after` actually
+/// grew every entry, the way summing in another instance's (non-negative)
+/// scaled bound always should.
+///
+/// # Remarks
+/// `fold_bound_scale` only guards the `u128` scale factor against
+/// overflow - it says nothing about whether `Bounds`'s own arithmetic can
+/// represent the resulting folded value. If an entry in `after` comes out
+/// *smaller* than the corresponding entry in `before`, `Bounds`'s
+/// `Add`/`Mul` impls wrapped internally instead of growing, so the folded
+/// matrix no longer fits what `Bounds` can represent - caught here rather
+/// than silently treated as a tighter (and therefore unsound) bound.
+fn folded_bounds_fit(before: &Matrix<Bounds>, after: &Matrix<Bounds>) -> bool
+where
+    Bounds: PartialOrd,
+{
+    before
+        .as_slice()
+        .iter()
+        .zip(after.as_slice())
+        .all(|(b, a)| a >= b)
+}
+
+/// Squeeze a ring element challenge out of the transcript. Folds the
+/// squeezed bytes down to a `u64` before lifting into `Q`; this trades a
+/// little of `Q`'s full challenge space for not needing to know
+/// `Q`'s byte width up front.
+fn squeeze_challenge<Q: From<u64>>(transcript: &mut Transcript) -> Q {
+    let mut bytes = [0u8; 8];
+    transcript.challenge_bytes(b"gamma", &mut bytes);
+
+    Q::from(u64::from_le_bytes(bytes))
+}
+
+fn matrix_to_bytes<Q: Into<u64> + Clone>(matrix: &Matrix<Polynomial<Q>>) -> Vec<u8> {
+    matrix
+        .as_slice()
+        .iter()
+        .flat_map(|poly| poly.coeffs.iter())
+        .flat_map(|c| c.clone().into().to_le_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squeeze_challenge_is_deterministic() {
+        let mut t1 = Transcript::new(b"sunscreen-logproof-batch");
+        t1.append_message(b"t_k", b"instance-one");
+        let gamma1: u64 = squeeze_challenge(&mut t1);
+
+        let mut t2 = Transcript::new(b"sunscreen-logproof-batch");
+        t2.append_message(b"t_k", b"instance-one");
+        let gamma2: u64 = squeeze_challenge(&mut t2);
+
+        assert_eq!(gamma1, gamma2);
+    }
+
+    #[test]
+    fn squeeze_challenge_differs_with_transcript_contents() {
+        let mut t1 = Transcript::new(b"sunscreen-logproof-batch");
+        t1.append_message(b"t_k", b"instance-one");
+        let gamma1: u64 = squeeze_challenge(&mut t1);
+
+        let mut t2 = Transcript::new(b"sunscreen-logproof-batch");
+        t2.append_message(b"t_k", b"instance-two");
+        let gamma2: u64 = squeeze_challenge(&mut t2);
+
+        assert_ne!(gamma1, gamma2);
+    }
+
+    #[test]
+    fn fold_bound_scale_multiplies_within_range() {
+        assert_eq!(fold_bound_scale(7, 6).unwrap(), 42);
+    }
+
+    #[test]
+    fn fold_bound_scale_compounds_across_several_folds() {
+        // Three folded instances: gamma_pow_scale goes 1 -> gamma ->
+        // gamma^2, matching how `batch` advances it once per rest instance.
+        let gamma_scale = (1u128 << 40) + 1;
+
+        let after_first = fold_bound_scale(1, gamma_scale).unwrap();
+        let after_second = fold_bound_scale(after_first, gamma_scale).unwrap();
+
+        assert_eq!(after_first, gamma_scale);
+        assert_eq!(after_second, gamma_scale * gamma_scale);
+    }
+
+    #[test]
+    fn fold_bound_scale_overflow_is_reported() {
+        assert_eq!(fold_bound_scale(u128::MAX, 2), Err(BatchError::BoundsOverflow));
+    }
+
+    #[test]
+    fn fold_bound_scale_does_not_overflow_at_challenge_widths_that_would_overflow_u64() {
+        // A full 64-bit challenge (close to `u64::MAX`) folded twice would
+        // already have overflowed a `u64` scale factor (see 5a00a95),
+        // since `gamma^2` alone exceeds `u64::MAX` - it must not overflow
+        // the wider `u128` scale factor this quickly.
+        let gamma_scale = u64::MAX as u128;
+
+        let once = fold_bound_scale(1, gamma_scale).unwrap();
+        let twice = fold_bound_scale(once, gamma_scale).unwrap();
+
+        assert_eq!(twice, gamma_scale * gamma_scale);
+    }
+}
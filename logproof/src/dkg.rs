@@ -0,0 +1,365 @@
+/**
+ * Bivariate-polynomial verifiable secret sharing, for distributing a BFV
+ * secret — the kind of witness held in
+ * [`LatticeProblem::s`](crate::test::LatticeProblem::s) — across parties
+ * without a trusted dealer, so Sunscreen can support threshold decryption
+ * where no single party ever holds the whole secret key.
+ *
+ * A dealer samples a symmetric bivariate polynomial `f(x, y) = sum_{0 <=
+ * i, j <= t} a_ij x^i y^j` (`a_ij == a_ji`) of degree `t` with `f(0, 0)`
+ * equal to the secret, and publishes Feldman commitments `g^{a_ij}` to its
+ * coefficients. Party `i` receives the row polynomial `f(i, y)` privately;
+ * parties `i` and `j` then exchange `f(i, j)` (party `i`'s row evaluated
+ * at `j`) and `f(j, i)` (equal by symmetry), each checking the received
+ * value against the public commitments. Any `t + 1` honest parties can
+ * evaluate their own row at `y = 0` and Lagrange-reconstruct `f(0, 0)`.
+ */
+use curve25519_dalek_ng::{constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint};
+use rand::RngCore;
+
+use crate::{
+    rings::ZqRistretto,
+    secret_witness::{MlockError, NestedRegions, SecretPayload, SecretWitness},
+};
+
+/**
+ * Finds the coefficient buffer inside a row of bivariate-polynomial
+ * coefficients, the same way [`Vec<i64>`'s impl in
+ * `secret_witness`](crate::secret_witness) does for converted BFV
+ * witnesses - so a [`SecretWitness`] wrapping the dealer's working
+ * coefficient matrix or a party's row can lock and zero the actual
+ * secret scalars.
+ */
+impl SecretPayload for Vec<ZqRistretto> {
+    fn nested_regions(&self) -> NestedRegions {
+        let mut regions = NestedRegions::default();
+
+        // Reported even when empty (e.g. the unused row `0`), like the
+        // `Vec<i64>` impl.
+        regions.leaf.push((
+            self.as_ptr() as usize,
+            self.capacity() * std::mem::size_of::<ZqRistretto>(),
+        ));
+
+        regions
+    }
+}
+
+/**
+ * A symmetric bivariate polynomial of degree `t`, privately held by the
+ * dealer, plus the public commitments to its coefficients.
+ */
+pub struct Dealing {
+    /// Feldman commitments to the coefficients `a_ij` for `0 <= i <= j <=
+    /// t`, stored densely (including the redundant `j < i` half, so
+    /// lookups don't need to special-case the symmetry).
+    pub commitments: Vec<Vec<RistrettoPoint>>,
+    /// Party `i`'s privately-held row polynomial `f(i, y)`, as coefficients
+    /// in ascending degree order. Index `0` is unused; parties are
+    /// numbered from `1`. Locked in memory and zeroized on drop, since a
+    /// party's row reconstructs straight to the shared secret once `t + 1`
+    /// of them are combined.
+    pub rows: SecretWitness<Vec<ZqRistretto>>,
+}
+
+/// An error verifying or reconstructing a share of a dealt secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DkgError {
+    /// Fewer than `t + 1` shares were supplied to [`reconstruct`].
+    NotEnoughShares {
+        /// The number of shares needed to reconstruct the secret.
+        needed: usize,
+        /// The number of shares actually supplied.
+        have: usize,
+    },
+    /// Two supplied shares came from the same party.
+    DuplicateParty(u64),
+}
+
+impl std::fmt::Display for DkgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughShares { needed, have } => write!(
+                f,
+                "need at least {} shares to reconstruct the secret, but only {} were given",
+                needed, have
+            ),
+            Self::DuplicateParty(party) => {
+                write!(f, "party {} contributed more than one share", party)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DkgError {}
+
+/**
+ * Deal `secret` as a degree-`threshold` symmetric bivariate polynomial
+ * among `num_parties` parties (numbered `1..=num_parties`), returning the
+ * public commitments and each party's private row polynomial.
+ */
+pub fn deal(
+    secret: ZqRistretto,
+    threshold: usize,
+    num_parties: usize,
+    rng: &mut impl RngCore,
+) -> Result<Dealing, MlockError> {
+    let degree = threshold;
+
+    // Sample the upper-triangular coefficients a_ij (i <= j) at random,
+    // pinning a_00 to the secret, then mirror into the lower triangle so
+    // f(x, y) == f(y, x). Locked and zeroized on drop like `rows` below -
+    // these coefficients are the same secret material, just not yet
+    // folded down to each party's share of it.
+    // Seeded with zeros, not `ZqRistretto::random(rng)` - `vec![x; n]`
+    // clones a single sampled value rather than drawing `n` independent
+    // ones, and the loop below overwrites every cell anyway.
+    let mut coeffs =
+        SecretWitness::new(vec![vec![ZqRistretto::from(0u64); degree + 1]; degree + 1])?;
+    let matrix = coeffs.as_mut_slice();
+
+    for i in 0..=degree {
+        for j in i..=degree {
+            matrix[i][j] = if i == 0 && j == 0 {
+                secret
+            } else {
+                ZqRistretto::random(rng)
+            };
+            matrix[j][i] = matrix[i][j];
+        }
+    }
+
+    let commitments = coeffs
+        .as_slice()
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|a_ij| RISTRETTO_BASEPOINT_POINT * a_ij.into_scalar())
+                .collect()
+        })
+        .collect();
+
+    // Party i's row polynomial f(i, y) = sum_j (sum_k a_kj i^k) y^j -
+    // evaluate the outer variable at each party index, keeping y symbolic.
+    let rows = (0..=num_parties)
+        .map(|i| {
+            if i == 0 {
+                return Vec::new();
+            }
+
+            let x = ZqRistretto::from(i as u64);
+
+            (0..=degree)
+                .map(|j| evaluate_univariate(&column(coeffs.as_slice(), j), x))
+                .collect()
+        })
+        .collect();
+
+    Ok(Dealing {
+        commitments,
+        rows: SecretWitness::new(rows)?,
+    })
+}
+
+/**
+ * Check that `value`, claimed by `from_party` to equal `f(from_party,
+ * party_index)`, is consistent with the dealer's public commitments.
+ *
+ * # Remarks
+ * `commitments` comes from whichever party dealt the secret, who may be
+ * the very party this check is trying to catch cheating; an empty or
+ * jagged matrix is rejected as `false` rather than indexed into, so a
+ * malformed dealing fails verification instead of panicking the caller.
+ */
+pub fn verify_share(
+    commitments: &[Vec<RistrettoPoint>],
+    from_party: u64,
+    party_index: u64,
+    value: ZqRistretto,
+) -> bool {
+    if commitments.is_empty() || commitments.iter().any(|row| row.len() != commitments.len()) {
+        return false;
+    }
+
+    let degree = commitments.len() - 1;
+    let x = ZqRistretto::from(from_party);
+    let y = ZqRistretto::from(party_index);
+
+    let mut expected = RistrettoPoint::default();
+    let mut x_pow = ZqRistretto::from(1u64);
+
+    for i in 0..=degree {
+        let mut y_pow = ZqRistretto::from(1u64);
+
+        for j in 0..=degree {
+            let weight = x_pow * y_pow;
+            expected += commitments[i][j] * weight.into_scalar();
+            y_pow = y_pow * y;
+        }
+
+        x_pow = x_pow * x;
+    }
+
+    expected == RISTRETTO_BASEPOINT_POINT * value.into_scalar()
+}
+
+/**
+ * Reconstruct the dealt secret `f(0, 0)` from `t + 1` parties' evaluations
+ * of their own row at `y = 0`, i.e. `(party_index, f(party_index, 0))`
+ * pairs, via Lagrange interpolation at `x = 0`.
+ */
+pub fn reconstruct(shares: &[(u64, ZqRistretto)], threshold: usize) -> Result<ZqRistretto, DkgError> {
+    let needed = threshold + 1;
+
+    if shares.len() < needed {
+        return Err(DkgError::NotEnoughShares {
+            needed,
+            have: shares.len(),
+        });
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for (party, _) in shares {
+        if !seen.insert(*party) {
+            return Err(DkgError::DuplicateParty(*party));
+        }
+    }
+
+    let points = &shares[..needed];
+    let zero = ZqRistretto::from(0u64);
+
+    let mut acc = ZqRistretto::from(0u64);
+
+    for (i, (x_i, y_i)) in points.iter().enumerate() {
+        let x_i = ZqRistretto::from(*x_i);
+        let mut term = *y_i;
+
+        for (j, (x_j, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            let x_j = ZqRistretto::from(*x_j);
+            term = term * (zero - x_j) * (x_i - x_j).invert();
+        }
+
+        acc = acc + term;
+    }
+
+    Ok(acc)
+}
+
+fn column(coeffs: &[Vec<ZqRistretto>], j: usize) -> Vec<ZqRistretto> {
+    coeffs.iter().map(|row| row[j]).collect()
+}
+
+/// Evaluate the polynomial with coefficients `coeffs` (ascending degree
+/// order) at `x`, via Horner's method.
+fn evaluate_univariate(coeffs: &[ZqRistretto], x: ZqRistretto) -> ZqRistretto {
+    coeffs
+        .iter()
+        .rev()
+        .fold(ZqRistretto::from(0u64), |acc, c| acc * x + *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    // `f(party, 0)`, the value a party contributes to reconstruction, is
+    // just the constant (`y^0`) coefficient of that party's row.
+    fn share_at_zero(dealing: &Dealing, party: u64) -> (u64, ZqRistretto) {
+        (party, dealing.rows.as_slice()[party as usize][0])
+    }
+
+    #[test]
+    fn reconstructs_secret_from_threshold_plus_one_shares() {
+        let mut rng = StepRng::new(1, 7);
+        let secret = ZqRistretto::from(424242u64);
+        let threshold = 2;
+        let dealing = deal(secret, threshold, 5, &mut rng).unwrap();
+
+        let shares: Vec<_> = (1..=3).map(|p| share_at_zero(&dealing, p)).collect();
+
+        assert_eq!(reconstruct(&shares, threshold).unwrap(), secret);
+    }
+
+    #[test]
+    fn reconstructs_same_secret_from_any_qualifying_subset() {
+        let mut rng = StepRng::new(5, 11);
+        let secret = ZqRistretto::from(99u64);
+        let threshold = 2;
+        let dealing = deal(secret, threshold, 5, &mut rng).unwrap();
+
+        let shares: Vec<_> = [2u64, 4, 5].iter().map(|&p| share_at_zero(&dealing, p)).collect();
+
+        assert_eq!(reconstruct(&shares, threshold).unwrap(), secret);
+    }
+
+    #[test]
+    fn verify_share_accepts_honestly_dealt_values() {
+        let mut rng = StepRng::new(3, 13);
+        let secret = ZqRistretto::from(7u64);
+        let threshold = 2;
+        let dealing = deal(secret, threshold, 4, &mut rng).unwrap();
+
+        // Party 1's row evaluated at party 3 must match what party 1 would
+        // actually send party 3.
+        let value = evaluate_univariate(&dealing.rows.as_slice()[1], ZqRistretto::from(3u64));
+
+        assert!(verify_share(&dealing.commitments, 1, 3, value));
+    }
+
+    #[test]
+    fn verify_share_rejects_tampered_value() {
+        let mut rng = StepRng::new(3, 13);
+        let secret = ZqRistretto::from(7u64);
+        let threshold = 2;
+        let dealing = deal(secret, threshold, 4, &mut rng).unwrap();
+
+        let value = evaluate_univariate(&dealing.rows.as_slice()[1], ZqRistretto::from(3u64));
+        let tampered = value + ZqRistretto::from(1u64);
+
+        assert!(!verify_share(&dealing.commitments, 1, 3, tampered));
+    }
+
+    #[test]
+    fn verify_share_rejects_empty_commitments() {
+        assert!(!verify_share(&[], 1, 3, ZqRistretto::from(0u64)));
+    }
+
+    #[test]
+    fn verify_share_rejects_jagged_commitments() {
+        let commitments = vec![
+            vec![RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_POINT],
+            vec![RISTRETTO_BASEPOINT_POINT],
+        ];
+
+        assert!(!verify_share(&commitments, 1, 3, ZqRistretto::from(0u64)));
+    }
+
+    #[test]
+    fn reconstruct_rejects_too_few_shares() {
+        let mut rng = StepRng::new(1, 7);
+        let dealing = deal(ZqRistretto::from(1u64), 2, 5, &mut rng).unwrap();
+
+        let shares: Vec<_> = (1..=2).map(|p| share_at_zero(&dealing, p)).collect();
+
+        assert_eq!(
+            reconstruct(&shares, 2),
+            Err(DkgError::NotEnoughShares { needed: 3, have: 2 })
+        );
+    }
+
+    #[test]
+    fn reconstruct_rejects_duplicate_party() {
+        let mut rng = StepRng::new(1, 7);
+        let dealing = deal(ZqRistretto::from(1u64), 2, 5, &mut rng).unwrap();
+
+        let mut shares: Vec<_> = (1..=3).map(|p| share_at_zero(&dealing, p)).collect();
+        shares[2] = shares[0];
+
+        assert_eq!(reconstruct(&shares, 2), Err(DkgError::DuplicateParty(1)));
+    }
+}
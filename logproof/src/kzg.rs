@@ -0,0 +1,311 @@
+/**
+ * A KZG polynomial commitment scheme over BLS12-381, offered as a succinct,
+ * constant-size alternative to the lattice-based logproof for committing to
+ * (and proving evaluations of) the polynomials in a [`LatticeProblem`](crate::test::LatticeProblem).
+ *
+ * A trusted-setup structured reference string (SRS) holds the powers of a
+ * toxic-waste scalar `tau` in `G1` (`g^tau^i` for `i` up to the supported
+ * degree) plus `g2^tau` in `G2`. A commitment to `p(x) = sum_i c_i x^i` is
+ * `C = sum_i g_i^{c_i}`; an opening at `z` is the commitment to the
+ * quotient `q(x) = (p(x) - p(z)) / (x - z)`, computed by synthetic
+ * division. Verification checks the pairing equation
+ * `e(C - g^{p(z)}, g2) = e(pi, g2^tau - g2^z)`.
+ */
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::Field;
+use group::Group;
+
+/**
+ * A trusted-setup structured reference string supporting commitments to
+ * polynomials of degree up to `max_degree`.
+ *
+ * # Remarks
+ * The scalar `tau` used to build this SRS must be discarded after setup;
+ * anyone who learns it can forge openings.
+ */
+pub struct Srs {
+    /// `g1^{tau^i}` for `i` in `0..=max_degree`.
+    powers_of_tau_g1: Vec<G1Projective>,
+    /// `g2^tau`.
+    tau_g2: G2Projective,
+}
+
+impl Srs {
+    /**
+     * Derive an SRS of the given `max_degree` from toxic-waste scalar
+     * `tau`. Intended for tests and ceremonies that combine several
+     * participants' contributions; production use should never construct
+     * this from a `tau` any single party knows in full.
+     */
+    pub fn setup(tau: Scalar, max_degree: usize) -> Self {
+        let mut powers_of_tau_g1 = Vec::with_capacity(max_degree + 1);
+        let mut power = Scalar::one();
+
+        for _ in 0..=max_degree {
+            powers_of_tau_g1.push(G1Projective::generator() * power);
+            power *= tau;
+        }
+
+        Self {
+            powers_of_tau_g1,
+            tau_g2: G2Projective::generator() * tau,
+        }
+    }
+
+    /// The largest polynomial degree this SRS can commit to.
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_tau_g1.len() - 1
+    }
+}
+
+/// A constant-size commitment to a polynomial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Commitment(G1Affine);
+
+/// A constant-size proof that a committed polynomial evaluates to a
+/// claimed value at a claimed point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Opening(G1Affine);
+
+/// An error committing to or opening a polynomial against an [`Srs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KzgError {
+    /// The polynomial's degree exceeds [`Srs::max_degree`].
+    DegreeTooLarge {
+        /// The polynomial's degree.
+        degree: usize,
+        /// The SRS's maximum supported degree.
+        max_degree: usize,
+    },
+}
+
+impl std::fmt::Display for KzgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DegreeTooLarge { degree, max_degree } => write!(
+                f,
+                "polynomial degree {} exceeds SRS max degree {}",
+                degree, max_degree
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KzgError {}
+
+/**
+ * Commit to a polynomial given as its coefficients in ascending degree
+ * order: `coeffs[i]` is the coefficient of `x^i`.
+ */
+pub fn commit(srs: &Srs, coeffs: &[Scalar]) -> Result<Commitment, KzgError> {
+    let degree = coeffs.len().saturating_sub(1);
+
+    if coeffs.len() > srs.powers_of_tau_g1.len() {
+        return Err(KzgError::DegreeTooLarge {
+            degree,
+            max_degree: srs.max_degree(),
+        });
+    }
+
+    let commitment = coeffs
+        .iter()
+        .zip(srs.powers_of_tau_g1.iter())
+        .fold(G1Projective::identity(), |acc, (c, g)| acc + g * c);
+
+    Ok(Commitment(commitment.into()))
+}
+
+/**
+ * Evaluate `coeffs` at `z` and produce an opening proof attesting to that
+ * evaluation.
+ */
+pub fn open(srs: &Srs, coeffs: &[Scalar], z: Scalar) -> Result<(Scalar, Opening), KzgError> {
+    if coeffs.len() > srs.powers_of_tau_g1.len() {
+        return Err(KzgError::DegreeTooLarge {
+            degree: coeffs.len().saturating_sub(1),
+            max_degree: srs.max_degree(),
+        });
+    }
+
+    let value = evaluate(coeffs, z);
+    let quotient = synthetic_divide(coeffs, z, value);
+
+    let proof = quotient
+        .iter()
+        .zip(srs.powers_of_tau_g1.iter())
+        .fold(G1Projective::identity(), |acc, (c, g)| acc + g * c);
+
+    Ok((value, Opening(proof.into())))
+}
+
+/**
+ * Verify that `commitment` opens to `value` at `z`, given `proof`.
+ */
+pub fn verify(srs: &Srs, commitment: Commitment, z: Scalar, value: Scalar, proof: Opening) -> bool {
+    let lhs_g1: G1Affine =
+        (G1Projective::from(commitment.0) - G1Projective::generator() * value).into();
+    let rhs_g2: G2Affine = (srs.tau_g2 - G2Projective::generator() * z).into();
+
+    let g2_generator = G2Affine::generator();
+
+    pairing(&lhs_g1, &g2_generator) == pairing(&proof.0, &rhs_g2)
+}
+
+/// Horner's method evaluation of `coeffs` at `z`.
+fn evaluate(coeffs: &[Scalar], z: Scalar) -> Scalar {
+    coeffs
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, c| acc * z + c)
+}
+
+/// Synthetic division of `p(x) - value` by `(x - z)`, returning the
+/// quotient's coefficients in ascending degree order. Assumes `value ==
+/// evaluate(coeffs, z)`, so the division is exact.
+fn synthetic_divide(coeffs: &[Scalar], z: Scalar, value: Scalar) -> Vec<Scalar> {
+    if coeffs.is_empty() {
+        return Vec::new();
+    }
+
+    let mut shifted = coeffs.to_vec();
+    shifted[0] -= value;
+
+    let mut quotient = vec![Scalar::zero(); shifted.len() - 1];
+    let mut carry = Scalar::zero();
+
+    for (i, coeff) in shifted.iter().enumerate().rev() {
+        if i == 0 {
+            break;
+        }
+
+        carry = *coeff + carry * z;
+        quotient[i - 1] = carry;
+    }
+
+    quotient
+}
+
+/**
+ * Convert the small signed coefficients produced by
+ * [`convert_to_small_coeffs`](crate::test::convert_to_small_coeffs) (or
+ * [`convert_to_smallint`](crate::test::convert_to_smallint)) into `Scalar`
+ * coefficients suitable for [`commit`] and [`open`].
+ */
+pub fn coeffs_to_polynomial(coeffs: &[i64]) -> Vec<Scalar> {
+    coeffs
+        .iter()
+        .map(|&c| {
+            if c < 0 {
+                -Scalar::from((-c) as u64)
+            } else {
+                Scalar::from(c as u64)
+            }
+        })
+        .collect()
+}
+
+/**
+ * Pack a byte payload into field-element coefficients, 31 bytes per
+ * [`Scalar`] (one byte short of BLS12-381's 32-byte scalar field so every
+ * chunk is guaranteed to fit below the field modulus).
+ */
+pub fn bytes_to_polynomial(bytes: &[u8]) -> Vec<Scalar> {
+    bytes
+        .chunks(31)
+        .map(|chunk| {
+            let mut buf = [0u8; 32];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Scalar::from_bytes(&buf).unwrap()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_srs(max_degree: usize) -> Srs {
+        Srs::setup(Scalar::from(1234567u64), max_degree)
+    }
+
+    #[test]
+    fn commit_open_verify_round_trip() {
+        let coeffs = vec![Scalar::from(3u64), Scalar::from(1u64), Scalar::from(4u64)];
+        let srs = test_srs(coeffs.len() - 1);
+
+        let commitment = commit(&srs, &coeffs).unwrap();
+
+        let z = Scalar::from(5u64);
+        let (value, proof) = open(&srs, &coeffs, z).unwrap();
+
+        assert_eq!(value, evaluate(&coeffs, z));
+        assert!(verify(&srs, commitment, z, value, proof));
+    }
+
+    #[test]
+    fn verify_rejects_wrong_value() {
+        let coeffs = vec![Scalar::from(3u64), Scalar::from(1u64), Scalar::from(4u64)];
+        let srs = test_srs(coeffs.len() - 1);
+
+        let commitment = commit(&srs, &coeffs).unwrap();
+        let z = Scalar::from(5u64);
+        let (value, proof) = open(&srs, &coeffs, z).unwrap();
+
+        assert!(!verify(&srs, commitment, z, value + Scalar::one(), proof));
+    }
+
+    #[test]
+    fn commit_rejects_degree_above_srs_max() {
+        let coeffs = vec![Scalar::from(1u64); 4];
+        let srs = test_srs(2);
+
+        assert_eq!(
+            commit(&srs, &coeffs),
+            Err(KzgError::DegreeTooLarge {
+                degree: 3,
+                max_degree: 2
+            })
+        );
+    }
+
+    #[test]
+    fn coeffs_to_polynomial_converts_signed_small_coeffs() {
+        let small_coeffs: Vec<i64> = vec![-5, 0, 42, -1];
+        let polynomial = coeffs_to_polynomial(&small_coeffs);
+
+        assert_eq!(polynomial[0], -Scalar::from(5u64));
+        assert_eq!(polynomial[2], Scalar::from(42u64));
+    }
+
+    #[test]
+    fn bytes_to_polynomial_packs_one_scalar_per_31_bytes() {
+        let bytes: Vec<u8> = (0..65).collect();
+
+        let coeffs = bytes_to_polynomial(&bytes);
+
+        // 65 bytes needs 3 chunks of up to 31 bytes each: 31 + 31 + 3.
+        assert_eq!(coeffs.len(), 3);
+
+        let mut expected_first = [0u8; 32];
+        expected_first[..31].copy_from_slice(&bytes[0..31]);
+        assert_eq!(coeffs[0], Scalar::from_bytes(&expected_first).unwrap());
+
+        let mut expected_last = [0u8; 32];
+        expected_last[..3].copy_from_slice(&bytes[62..65]);
+        assert_eq!(coeffs[2], Scalar::from_bytes(&expected_last).unwrap());
+    }
+
+    #[test]
+    fn bytes_to_polynomial_round_trips_through_commit_open_verify() {
+        let bytes = b"the quick brown fox jumps over the lazy dog";
+        let coeffs = bytes_to_polynomial(bytes);
+        let srs = test_srs(coeffs.len() - 1);
+
+        let commitment = commit(&srs, &coeffs).unwrap();
+        let z = Scalar::from(7u64);
+        let (value, proof) = open(&srs, &coeffs, z).unwrap();
+
+        assert_eq!(value, evaluate(&coeffs, z));
+        assert!(verify(&srs, commitment, z, value, proof));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod batch;
+pub mod dkg;
+pub mod kzg;
+pub mod ntt;
+pub mod rs_code;
+pub mod secret_witness;
+pub mod test;
@@ -0,0 +1,503 @@
+/**
+ * A negacyclic number-theoretic transform (NTT) for fast polynomial
+ * multiplication modulo `X^N + 1`.
+ *
+ * For a prime modulus `q` with `2N | (q - 1)`, multiplying `a * b mod
+ * (X^N + 1)` can be done in `O(N log N)` instead of the schoolbook
+ * `O(N^2)`: scale each coefficient vector by powers of a primitive `2N`-th
+ * root of unity `psi`, run a forward NTT using the powers of `omega =
+ * psi^2`, multiply pointwise, inverse-transform, then unscale by powers of
+ * `psi^-1` and by `N^-1`.
+ */
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use sunscreen_math::{
+    poly::Polynomial,
+    ring::{ArithmeticBackend, Zq},
+};
+
+/**
+ * Extension point for multiplying two polynomials modulo `X^N + 1` using
+ * the NTT when the ring supports it.
+ */
+pub trait NttMul: Sized {
+    /**
+     * Multiply `self` by `rhs` modulo `X^N + 1`, using the NTT when the
+     * ring's modulus has a primitive `2N`-th root of unity, falling back
+     * to schoolbook negacyclic multiplication otherwise.
+     */
+    fn mul_ntt(&self, rhs: &Self) -> Self;
+}
+
+impl<const N: usize, B> NttMul for Polynomial<Zq<N, B>>
+where
+    B: ArithmeticBackend<N>,
+    Zq<N, B>: Into<u64> + From<u64>,
+{
+    fn mul_ntt(&self, rhs: &Self) -> Self {
+        let q = B::MODULUS;
+
+        // `N` here is `Zq<N, B>`'s limb count, not the ring dimension
+        // `X^n + 1` - the transform length is the polynomial's own
+        // coefficient count.
+        let n = self.coeffs.len().max(rhs.coeffs.len());
+
+        let a = to_u64_coeffs(self, n);
+        let b = to_u64_coeffs(rhs, n);
+
+        let product = match negacyclic_twiddles(q, n) {
+            Some(twiddles) => ntt_negacyclic_mul(&a, &b, q, twiddles),
+            None => negacyclic_mul_naive(&a, &b, q),
+        };
+
+        Polynomial {
+            coeffs: product.into_iter().map(Zq::<N, B>::from).collect(),
+        }
+    }
+}
+
+fn to_u64_coeffs<Q: Into<u64> + Clone>(poly: &Polynomial<Q>, n: usize) -> Vec<u64> {
+    let mut coeffs = vec![0u64; n];
+
+    for (c, p) in coeffs.iter_mut().zip(poly.coeffs.iter()) {
+        *c = p.clone().into();
+    }
+
+    coeffs
+}
+
+fn negacyclic_mul_naive(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+    let n = a.len();
+    let mut result = vec![0u64; n];
+
+    for (i, &a_i) in a.iter().enumerate() {
+        if a_i == 0 {
+            continue;
+        }
+
+        for (j, &b_j) in b.iter().enumerate() {
+            let term = mulmod(a_i, b_j, q);
+            let k = i + j;
+
+            if k < n {
+                result[k] = addmod(result[k], term, q);
+            } else {
+                // X^n == -1 mod (X^n + 1)
+                result[k - n] = submod(result[k - n], term, q);
+            }
+        }
+    }
+
+    result
+}
+
+/// Twiddle factor tables for a given `(q, N)` pair, computed once and
+/// cached so repeated multiplications in the same ring don't re-pay the
+/// cost of finding a root of unity and building its power table.
+#[derive(Clone)]
+struct Twiddles {
+    /// `psi^i mod q`, used to fold the negacyclic product into a cyclic
+    /// one before the forward transform.
+    psi_powers: Vec<u64>,
+    /// `psi^{-i} mod q`, used to unfold after the inverse transform.
+    psi_inv_powers: Vec<u64>,
+    /// `omega^i mod q` where `omega = psi^2`, the forward transform's
+    /// twiddle factors.
+    omega_powers: Vec<u64>,
+    /// `omega^{-i} mod q`, the inverse transform's twiddle factors.
+    omega_inv_powers: Vec<u64>,
+    /// `N^{-1} mod q`.
+    n_inv: u64,
+}
+
+fn twiddle_cache() -> &'static Mutex<HashMap<(u64, usize), Twiddles>> {
+    static CACHE: OnceLock<Mutex<HashMap<(u64, usize), Twiddles>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Returns the cached (or newly computed) twiddle tables for `(q, n)`, or
+/// `None` if `q` isn't prime with `2n | (q - 1)`, i.e. has no primitive
+/// `2n`-th root of unity.
+fn negacyclic_twiddles(q: u64, n: usize) -> Option<Twiddles> {
+    // The Cooley-Tukey butterflies below, the bit-reversal permutation,
+    // and `find_primitive_root`'s "is this root's order exactly `order`"
+    // shortcut are only valid when `n` is a power of two. A
+    // non-power-of-two `n` isn't caught by the "no primitive root"
+    // check below (`2n | (q - 1)` doesn't require `n` itself to be a
+    // power of two), so it needs its own real, release-mode check here -
+    // falling through to the naive path is exactly what callers already
+    // do for "no root of unity", so reuse that by returning `None`.
+    if !n.is_power_of_two() {
+        return None;
+    }
+
+    // `find_primitive_root` and `invmod` both assume `q` is prime -
+    // `invmod` in particular leans on Fermat's little theorem, which
+    // only holds mod a prime. A composite `q` that happens to satisfy
+    // `2n | (q - 1)` would otherwise sail through `find_primitive_root`
+    // and come out with a bogus "root" and a wrong inverse, silently
+    // corrupting the transform instead of falling back to the naive path.
+    if !is_prime(q) {
+        return None;
+    }
+
+    if let Some(cached) = twiddle_cache().lock().unwrap().get(&(q, n)) {
+        return Some(cached.clone());
+    }
+
+    let psi = find_primitive_root(q, 2 * n as u64)?;
+    let psi_inv = invmod(psi, q);
+    let omega = mulmod(psi, psi, q);
+    let omega_inv = invmod(omega, q);
+    let n_inv = invmod(n as u64 % q, q);
+
+    let twiddles = Twiddles {
+        psi_powers: powers(psi, n, q),
+        psi_inv_powers: powers(psi_inv, n, q),
+        omega_powers: powers(omega, n, q),
+        omega_inv_powers: powers(omega_inv, n, q),
+        n_inv,
+    };
+
+    twiddle_cache()
+        .lock()
+        .unwrap()
+        .insert((q, n), twiddles.clone());
+
+    Some(twiddles)
+}
+
+fn ntt_negacyclic_mul(a: &[u64], b: &[u64], q: u64, twiddles: Twiddles) -> Vec<u64> {
+    let n = a.len();
+
+    let mut a_scaled: Vec<u64> = a
+        .iter()
+        .zip(twiddles.psi_powers.iter())
+        .map(|(&x, &p)| mulmod(x, p, q))
+        .collect();
+    let mut b_scaled: Vec<u64> = b
+        .iter()
+        .zip(twiddles.psi_powers.iter())
+        .map(|(&x, &p)| mulmod(x, p, q))
+        .collect();
+
+    forward_ntt(&mut a_scaled, q, &twiddles.omega_powers);
+    forward_ntt(&mut b_scaled, q, &twiddles.omega_powers);
+
+    let mut product: Vec<u64> = a_scaled
+        .iter()
+        .zip(b_scaled.iter())
+        .map(|(&x, &y)| mulmod(x, y, q))
+        .collect();
+
+    inverse_ntt(&mut product, q, &twiddles.omega_inv_powers);
+
+    product
+        .iter()
+        .zip(twiddles.psi_inv_powers.iter())
+        .map(|(&x, &p)| mulmod(mulmod(x, p, q), twiddles.n_inv, q))
+        .take(n)
+        .collect()
+}
+
+/// In-place iterative Cooley-Tukey forward NTT. `n = values.len()` must be
+/// a power of two, and `omega_powers[i] = omega^i mod q` for `i in 0..n`.
+fn forward_ntt(values: &mut [u64], q: u64, omega_powers: &[u64]) {
+    let n = values.len();
+    bit_reverse_permute(values);
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = n / len;
+
+        for block in values.chunks_mut(len) {
+            for i in 0..half {
+                let w = omega_powers[i * step];
+                let u = block[i];
+                let t = mulmod(block[i + half], w, q);
+
+                block[i] = addmod(u, t, q);
+                block[i + half] = submod(u, t, q);
+            }
+        }
+
+        len *= 2;
+    }
+}
+
+/// In-place iterative Gentleman-Sande inverse NTT (without the final `N^-1`
+/// scaling, which the caller applies alongside the `psi^-1` unscaling).
+fn inverse_ntt(values: &mut [u64], q: u64, omega_inv_powers: &[u64]) {
+    let n = values.len();
+
+    let mut len = n;
+    while len >= 2 {
+        let half = len / 2;
+        let step = n / len;
+
+        for block in values.chunks_mut(len) {
+            for i in 0..half {
+                let w = omega_inv_powers[i * step];
+                let u = block[i];
+                let v = block[i + half];
+
+                block[i] = addmod(u, v, q);
+                block[i + half] = mulmod(submod(u, v, q), w, q);
+            }
+        }
+
+        len /= 2;
+    }
+
+    bit_reverse_permute(values);
+}
+
+fn bit_reverse_permute(values: &mut [u64]) {
+    let n = values.len();
+
+    // `n <= 1` has nothing to permute, and `n == 1` would otherwise shift
+    // by `u32::BITS`, which is out of range.
+    if n <= 1 {
+        return;
+    }
+
+    let bits = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = (i as u32).reverse_bits() >> (u32::BITS - bits) as u32;
+        let j = j as usize;
+
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+fn powers(base: u64, count: usize, q: u64) -> Vec<u64> {
+    let mut result = Vec::with_capacity(count);
+    let mut acc = 1u64;
+
+    for _ in 0..count {
+        result.push(acc);
+        acc = mulmod(acc, base, q);
+    }
+
+    result
+}
+
+/// Finds a primitive `order`-th root of unity mod the prime `q`, or `None`
+/// if `order` doesn't divide `q - 1`.
+fn find_primitive_root(q: u64, order: u64) -> Option<u64> {
+    if (q - 1) % order != 0 {
+        return None;
+    }
+
+    let exponent = (q - 1) / order;
+
+    // `q` is prime, so almost every residue is a generator of `Z_q^*`; try
+    // small candidates until one yields an element of the exact order.
+    for candidate in 2..q {
+        let root = powmod(candidate, exponent, q);
+
+        if powmod(root, order / 2, q) != 1 {
+            return Some(root);
+        }
+    }
+
+    None
+}
+
+fn addmod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 + b as u128) % q as u128) as u64
+}
+
+fn submod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 + q as u128 - b as u128 % q as u128) % q as u128) as u64
+}
+
+fn mulmod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 * b as u128) % q as u128) as u64
+}
+
+fn powmod(mut base: u64, mut exponent: u64, q: u64) -> u64 {
+    let mut result = 1u64;
+    base %= q;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mulmod(result, base, q);
+        }
+
+        base = mulmod(base, base, q);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// Modular inverse of `a` mod the prime `q`, via Fermat's little theorem.
+fn invmod(a: u64, q: u64) -> u64 {
+    powmod(a, q - 2, q)
+}
+
+/// A deterministic Miller-Rabin primality test. The witness set `{2, 3,
+/// 5, 7, 11, 13, 17, 19, 23, 29, 31, 37}` is proven to correctly decide
+/// primality for every `u64`, so unlike a probabilistic Miller-Rabin run
+/// this never reports a composite as prime.
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    for &p in &WITNESSES {
+        if n == p {
+            return true;
+        }
+        if n % p == 0 {
+            return false;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut r = 0u32;
+    while d % 2 == 0 {
+        d /= 2;
+        r += 1;
+    }
+
+    'witness: for &a in &WITNESSES {
+        let mut x = powmod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        for _ in 0..r - 1 {
+            x = mulmod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ntt_mul_matches_naive_negacyclic_mul() {
+        // q = 17, N = 4: 2N = 8 divides q - 1 = 16, so 17 has a
+        // primitive 8th root of unity.
+        let q = 17u64;
+        let a = vec![1u64, 2, 3, 4];
+        let b = vec![5u64, 6, 7, 8];
+
+        let naive = negacyclic_mul_naive(&a, &b, q);
+
+        let twiddles = negacyclic_twiddles(q, a.len()).expect("q has the required root of unity");
+        let via_ntt = ntt_negacyclic_mul(&a, &b, q, twiddles);
+
+        assert_eq!(naive, via_ntt);
+    }
+
+    #[test]
+    fn missing_root_of_unity_returns_none() {
+        // q = 7: q - 1 = 6 isn't divisible by 2N = 8, so there's no
+        // primitive 8th root of unity mod 7.
+        assert!(negacyclic_twiddles(7, 4).is_none());
+    }
+
+    #[test]
+    fn non_power_of_two_n_returns_none() {
+        // q = 13: 2N = 6 divides q - 1 = 12, so a naive "has a root of
+        // unity" check alone would accept this N = 3 - but the
+        // power-of-two-only Cooley-Tukey path below can't handle it.
+        assert!(negacyclic_twiddles(13, 3).is_none());
+    }
+
+    #[test]
+    fn composite_modulus_satisfying_the_congruence_returns_none() {
+        // q = 9 = 3^2: N = 4 is a power of two and 2N = 8 divides q - 1 =
+        // 8, so the congruence check alone would accept it - but 9 isn't
+        // prime, so `find_primitive_root`/`invmod`'s prime-only math
+        // would silently produce a wrong "root of unity" instead.
+        assert!(!is_prime(9));
+        assert!(negacyclic_twiddles(9, 4).is_none());
+    }
+
+    #[test]
+    fn is_prime_rejects_small_composites_and_accepts_small_primes() {
+        for p in [2u64, 3, 5, 7, 11, 13, 17, 97, 7919] {
+            assert!(is_prime(p), "{p} should be prime");
+        }
+
+        for c in [0u64, 1, 4, 6, 8, 9, 15, 21, 25, 49] {
+            assert!(!is_prime(c), "{c} should be composite");
+        }
+    }
+
+    #[test]
+    fn bit_reverse_permute_is_its_own_inverse() {
+        let mut values = vec![10u64, 20, 30, 40];
+        let original = values.clone();
+
+        bit_reverse_permute(&mut values);
+        bit_reverse_permute(&mut values);
+
+        assert_eq!(values, original);
+    }
+
+    #[test]
+    fn bit_reverse_permute_handles_trivial_lengths() {
+        let mut empty: Vec<u64> = Vec::new();
+        bit_reverse_permute(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![42u64];
+        bit_reverse_permute(&mut single);
+        assert_eq!(single, vec![42]);
+    }
+
+    /// A stand-in `ArithmeticBackend` used only to exercise
+    /// `Polynomial<Zq<N, B>>::mul_ntt` itself - `N = 2` here is the
+    /// ring element's limb count, deliberately smaller than the 8-term
+    /// polynomials below, so a transform length wrongly derived from `N`
+    /// instead of `self.coeffs.len()` would truncate them and this test
+    /// would fail.
+    struct TestBackend;
+
+    impl ArithmeticBackend<2> for TestBackend {
+        const MODULUS: u64 = 17;
+    }
+
+    #[test]
+    fn mul_ntt_uses_polynomial_length_not_zq_limb_count() {
+        let identity = Polynomial {
+            coeffs: [1u64, 0, 0, 0, 0, 0, 0, 0]
+                .into_iter()
+                .map(Zq::<2, TestBackend>::from)
+                .collect(),
+        };
+        let a = Polynomial {
+            coeffs: [1u64, 2, 3, 4, 5, 6, 7, 8]
+                .into_iter()
+                .map(Zq::<2, TestBackend>::from)
+                .collect(),
+        };
+
+        let product = a.mul_ntt(&identity);
+
+        let expected: Vec<u64> = a.coeffs.iter().cloned().map(Into::into).collect();
+        let actual: Vec<u64> = product.coeffs.iter().cloned().map(Into::into).collect();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,292 @@
+/**
+ * Reed-Solomon erasure coding for the coefficient rows produced by
+ * [`convert_to_smallint`](crate::test::convert_to_smallint).
+ *
+ * Each row's `k` coefficients are treated as the values of an implicit
+ * degree-`(k-1)` polynomial at the domain points `1..=k`. Evaluating that
+ * polynomial at `n >= k` domain points produces `n` redundant shares; any
+ * `k` of them are enough to recover the original row via Lagrange
+ * interpolation. This gives a data-availability mode for spreading large
+ * serialized ciphertext or proof blobs across parties: lose up to `n - k`
+ * shares and the row is still recoverable.
+ *
+ * Arithmetic is carried out over `GF(p)` for the Mersenne prime `p = 2^61
+ * - 1`, which comfortably holds the small coefficients `convert_to_smallint`
+ * produces plus the evaluation-point bookkeeping.
+ */
+
+/// `2^61 - 1`, a Mersenne prime large enough to hold the small coefficients
+/// `convert_to_smallint` produces.
+const FIELD_MODULUS: i128 = (1 << 61) - 1;
+
+/// One evaluation of a row's implicit polynomial at a domain point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// The domain point this share was evaluated at.
+    pub x: i64,
+    /// The polynomial's value at `x`.
+    pub y: i64,
+}
+
+/// An error encoding or decoding a row of coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RsCodeError {
+    /// Fewer than `k` shares were supplied to [`rs_decode`].
+    NotEnoughShares {
+        /// The number of shares needed to reconstruct the row.
+        needed: usize,
+        /// The number of shares actually supplied.
+        have: usize,
+    },
+    /// The requested evaluation domain doesn't have `n` distinct points.
+    InsufficientDomain {
+        /// The number of domain points requested.
+        requested: usize,
+        /// The number of distinct points the field actually has room for.
+        available: usize,
+    },
+    /// Two supplied shares had the same evaluation point, so they can't
+    /// both contribute to the interpolation.
+    DuplicateEvaluationPoint(i64),
+    /// `coeffs.len()` didn't match the number of data points `k` the
+    /// caller asked to encode against.
+    MismatchedCoeffsLen {
+        /// The number of coefficients `k` that was requested.
+        expected: usize,
+        /// The number of coefficients actually supplied.
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for RsCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughShares { needed, have } => write!(
+                f,
+                "need at least {} shares to reconstruct the row, but only {} were given",
+                needed, have
+            ),
+            Self::InsufficientDomain {
+                requested,
+                available,
+            } => write!(
+                f,
+                "requested {} distinct evaluation points, but only {} are available",
+                requested, available
+            ),
+            Self::DuplicateEvaluationPoint(x) => {
+                write!(f, "evaluation point {} was supplied more than once", x)
+            }
+            Self::MismatchedCoeffsLen { expected, actual } => write!(
+                f,
+                "expected {} coefficients to encode against k, but got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RsCodeError {}
+
+/**
+ * Encode `coeffs` (treated as the `k` values of an implicit degree-`(k-1)`
+ * polynomial at domain points `1..=k`) into `n` redundant shares, evaluated
+ * at domain points `1..=n`.
+ */
+pub fn rs_encode(coeffs: &[i64], k: usize, n: usize) -> Result<Vec<Share>, RsCodeError> {
+    if coeffs.len() != k {
+        return Err(RsCodeError::MismatchedCoeffsLen {
+            expected: k,
+            actual: coeffs.len(),
+        });
+    }
+
+    if (n as i128) >= FIELD_MODULUS {
+        return Err(RsCodeError::InsufficientDomain {
+            requested: n,
+            available: FIELD_MODULUS as usize,
+        });
+    }
+
+    let data_points: Vec<Share> = coeffs
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| Share {
+            x: (i + 1) as i64,
+            y,
+        })
+        .collect();
+
+    (1..=n as i64)
+        .map(|x| {
+            Ok(Share {
+                x,
+                y: lagrange_eval(&data_points[..k], x) as i64,
+            })
+        })
+        .collect()
+}
+
+/**
+ * Reconstruct the original `k`-coefficient row from any `k` surviving
+ * shares, via Lagrange interpolation.
+ */
+pub fn rs_decode(shares: &[Share], k: usize) -> Result<Vec<i64>, RsCodeError> {
+    if shares.len() < k {
+        return Err(RsCodeError::NotEnoughShares {
+            needed: k,
+            have: shares.len(),
+        });
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for share in shares {
+        if !seen.insert(share.x) {
+            return Err(RsCodeError::DuplicateEvaluationPoint(share.x));
+        }
+    }
+
+    let basis = &shares[..k];
+
+    (1..=k as i64)
+        .map(|x| Ok(to_balanced(lagrange_eval(basis, x))))
+        .collect()
+}
+
+/// Evaluate the unique degree-`(points.len() - 1)` polynomial through
+/// `points` at `x`, via direct Lagrange evaluation. The result is the
+/// field element's canonical, non-negative representative in `[0,
+/// FIELD_MODULUS)`.
+fn lagrange_eval(points: &[Share], x: i64) -> i128 {
+    let x = reduce(x as i128);
+
+    let mut acc = 0i128;
+
+    for (i, p_i) in points.iter().enumerate() {
+        let x_i = reduce(p_i.x as i128);
+        let mut term = reduce(p_i.y as i128);
+
+        for (j, p_j) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            let x_j = reduce(p_j.x as i128);
+            let numerator = field_sub(x, x_j);
+            let denominator = field_sub(x_i, x_j);
+
+            term = field_mul(term, field_mul(numerator, field_inv(denominator)));
+        }
+
+        acc = field_add(acc, term);
+    }
+
+    acc
+}
+
+/// Map a field element's canonical, non-negative representative back to
+/// the balanced signed representative `convert_to_smallint` produces:
+/// values past the halfway point of the field wrap around to negative.
+/// Without this, any originally-negative coefficient would round-trip
+/// through `rs_encode`/`rs_decode` as `FIELD_MODULUS - |value|` instead of
+/// its original value.
+fn to_balanced(r: i128) -> i64 {
+    if r > FIELD_MODULUS / 2 {
+        (r - FIELD_MODULUS) as i64
+    } else {
+        r as i64
+    }
+}
+
+fn reduce(a: i128) -> i128 {
+    a.rem_euclid(FIELD_MODULUS)
+}
+
+fn field_add(a: i128, b: i128) -> i128 {
+    (a + b) % FIELD_MODULUS
+}
+
+fn field_sub(a: i128, b: i128) -> i128 {
+    (a - b).rem_euclid(FIELD_MODULUS)
+}
+
+fn field_mul(a: i128, b: i128) -> i128 {
+    (a * b).rem_euclid(FIELD_MODULUS)
+}
+
+fn field_inv(a: i128) -> i128 {
+    field_pow(a, FIELD_MODULUS - 2)
+}
+
+fn field_pow(mut base: i128, mut exponent: i128) -> i128 {
+    let mut result = 1i128;
+    base = reduce(base);
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = field_mul(result, base);
+        }
+
+        base = field_mul(base, base);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_negative_coefficients() {
+        let coeffs = vec![-5i64, 12, -1, 3];
+        let shares = rs_encode(&coeffs, 4, 7).unwrap();
+
+        let decoded = rs_decode(&shares[1..5], 4).unwrap();
+
+        assert_eq!(decoded, coeffs);
+    }
+
+    #[test]
+    fn reconstructs_from_any_k_of_n_shares() {
+        let coeffs = vec![10i64, -20, 30];
+        let shares = rs_encode(&coeffs, 3, 6).unwrap();
+
+        // Drop the first two shares; the remaining 4 are more than enough.
+        let surviving = &shares[2..];
+        let decoded = rs_decode(surviving, 3).unwrap();
+
+        assert_eq!(decoded, coeffs);
+    }
+
+    #[test]
+    fn encode_rejects_mismatched_coeffs_len() {
+        let coeffs = vec![1i64, 2];
+
+        assert_eq!(
+            rs_encode(&coeffs, 3, 5),
+            Err(RsCodeError::MismatchedCoeffsLen {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn decode_rejects_too_few_shares() {
+        let shares = vec![Share { x: 1, y: 10 }];
+
+        assert_eq!(
+            rs_decode(&shares, 2),
+            Err(RsCodeError::NotEnoughShares { needed: 2, have: 1 })
+        );
+    }
+
+    #[test]
+    fn decode_rejects_duplicate_evaluation_points() {
+        let shares = vec![Share { x: 1, y: 10 }, Share { x: 1, y: 20 }];
+
+        assert_eq!(rs_decode(&shares, 2), Err(RsCodeError::DuplicateEvaluationPoint(1)));
+    }
+}
@@ -0,0 +1,448 @@
+/**
+ * A zeroizing, memory-locked container for secret witness material.
+ *
+ * [`SecretWitness`] wraps a `Vec<T>` and asks the OS to keep the backing
+ * pages resident for the lifetime of the allocation (`mlock` on Unix,
+ * `VirtualLock` on Windows), so the secret coefficients it holds are never
+ * paged out to swap. On drop, every locked leaf payload region is zeroed
+ * in place before the pages are unlocked, so the secret doesn't linger in
+ * memory once the allocation is released or reused.
+ */
+use std::{fmt, io, mem};
+
+use zeroize::Zeroize;
+
+/// The starting address and byte length of one heap allocation.
+type Region = (usize, usize);
+
+/**
+ * The heap allocations a secret-bearing type owns beyond its own stack
+ * representation, as found by [`SecretPayload::nested_regions`].
+ */
+#[derive(Default)]
+pub struct NestedRegions {
+    /// Buffers holding actual secret values, e.g. a row's raw coefficients
+    /// or a polynomial's ring-element coefficients. Locked *and* zeroed on
+    /// drop.
+    pub leaf: Vec<Region>,
+    /// Intermediate buffers that only hold bookkeeping - pointers,
+    /// lengths, capacities of further nested allocations (e.g. the array
+    /// backing a `Vec` of `Vec`s). Locked, but never zeroed directly:
+    /// doing so would overwrite the pointers those nested allocations'
+    /// own `Drop` impls need in order to deallocate correctly.
+    pub bookkeeping: Vec<Region>,
+}
+
+/**
+ * Implemented by types [`SecretWitness`] knows how to find the real
+ * secret-bearing heap buffers of.
+ *
+ * # Remarks
+ * `T: Zeroize` alone isn't enough for [`SecretWitness`] to protect `T`
+ * against swapping: `Zeroize` can recurse through a composite `T` just
+ * fine on drop, but it never tells `SecretWitness` *where in memory*
+ * those nested allocations live, which is what `mlock` needs to know.
+ * Requiring `T: Zeroize` on the public struct would also force every
+ * caller touching `SecretWitness<T>` to prove `T: Zeroize` for whatever
+ * upstream type `T` happens to be, even when no such impl exists; this
+ * trait is implemented locally instead, so `SecretWitness` never takes on
+ * a foreign-trait bound it can't discharge.
+ */
+pub trait SecretPayload {
+    /// Every heap buffer `self` owns beyond its own representation.
+    fn nested_regions(&self) -> NestedRegions;
+}
+
+impl SecretPayload for i64 {
+    fn nested_regions(&self) -> NestedRegions {
+        NestedRegions::default()
+    }
+}
+
+impl SecretPayload for Vec<i64> {
+    fn nested_regions(&self) -> NestedRegions {
+        let mut regions = NestedRegions::default();
+
+        // Reported even when empty: a zero-length region is never locked,
+        // but its presence tells `SecretWitness::new` that the spine holds
+        // `Vec` headers rather than secret bytes, so it is not zeroed.
+        regions
+            .leaf
+            .push((self.as_ptr() as usize, self.capacity() * mem::size_of::<i64>()));
+
+        regions
+    }
+}
+
+/**
+ * A secret buffer whose backing memory is locked against swapping for as
+ * long as it's alive, and zeroized on drop.
+ */
+pub struct SecretWitness<T: SecretPayload> {
+    data: Vec<T>,
+    /// Every region that was `mlock`ed by [`SecretWitness::new`] and must
+    /// be `munlock`ed on drop.
+    locked_regions: Vec<Region>,
+    /// The subset of `locked_regions` holding actual secret bytes, zeroed
+    /// on drop before the regions are unlocked.
+    leaf_regions: Vec<Region>,
+}
+
+impl<T: SecretPayload> SecretWitness<T> {
+    /**
+     * Take ownership of `data`, locking its backing allocation, and every
+     * nested allocation reported by `T::nested_regions`, in memory.
+     *
+     * # Remarks
+     * If an allocation has zero capacity (e.g. an empty `Vec`), no lock is
+     * attempted for it, since there's nothing to protect. If a lock
+     * attempt fails partway through, every lock already taken is released
+     * before returning the error - and since `data` already holds live
+     * secret coefficients at that point, every leaf region captured so far
+     * is zeroed first, the same as a successfully constructed witness's
+     * `Drop` would do, so the failure path never leaks the secret into an
+     * ordinary `Vec::drop`.
+     */
+    pub fn new(data: Vec<T>) -> Result<Self, MlockError> {
+        // Work out the full set of leaf/bookkeeping regions up front,
+        // before attempting to lock any of them, so that a lock failure
+        // partway through still leaves us knowing every leaf region that
+        // exists - not just the ones that happened to get locked before
+        // the failure - and can zero all of them rather than leaking
+        // whichever ones came after the failing call.
+        let spine = (data.as_ptr() as usize, data.capacity() * mem::size_of::<T>());
+        let mut bookkeeping_regions = Vec::new();
+        let mut leaf_regions = Vec::new();
+
+        // Whether any item reported a nested leaf region of its own. If
+        // none did, `T` has no heap allocation beyond the spine, which
+        // means the secret bytes *are* the spine - e.g. `SecretWitness<i64>`
+        // stores its secret values directly in the backing `Vec<i64>`, not
+        // behind a pointer a `nested_regions` impl could report.
+        let mut found_nested_leaf = false;
+
+        for item in &data {
+            let nested = item.nested_regions();
+
+            bookkeeping_regions.extend(nested.bookkeeping.into_iter().filter(|r| r.1 > 0));
+
+            for region in nested.leaf {
+                found_nested_leaf = true;
+
+                if region.1 > 0 {
+                    leaf_regions.push(region);
+                }
+            }
+        }
+
+        if !found_nested_leaf && spine.1 > 0 {
+            leaf_regions.push(spine);
+        }
+
+        let mut locked_regions = Vec::new();
+
+        let lock_all = (|| -> Result<(), MlockError> {
+            if spine.1 > 0 {
+                lock_memory(spine.0, spine.1)?;
+                locked_regions.push(spine);
+            }
+
+            for &region in &bookkeeping_regions {
+                lock_memory(region.0, region.1)?;
+                locked_regions.push(region);
+            }
+
+            for &region in &leaf_regions {
+                if region == spine {
+                    continue;
+                }
+
+                lock_memory(region.0, region.1)?;
+                locked_regions.push(region);
+            }
+
+            Ok(())
+        })();
+
+        if let Err(err) = lock_all {
+            for &(address, byte_count) in &leaf_regions {
+                // Safety: `data` (and everything it owns) is still alive
+                // here regardless of how far the locking loop above got -
+                // we're about to return it to the caller to be dropped as
+                // an ordinary `Vec`, so zero the secret bytes first.
+                let bytes =
+                    unsafe { std::slice::from_raw_parts_mut(address as *mut u8, byte_count) };
+                bytes.zeroize();
+            }
+
+            for (address, byte_count) in locked_regions {
+                let _ = unlock_memory(address, byte_count);
+            }
+
+            return Err(err);
+        }
+
+        Ok(Self {
+            data,
+            locked_regions,
+            leaf_regions,
+        })
+    }
+
+    /**
+     * Lock a single value, for the common case where `T` already
+     * represents a whole composite secret (e.g. `Matrix<Polynomial<Q>>`
+     * standing in for an entire witness matrix `S`) rather than a batch
+     * of independent rows.
+     *
+     * # Remarks
+     * Equivalent to `SecretWitness::new(vec![value])`, so callers
+     * don't need to reach for `vec![..]` just to wrap a witness that
+     * only ever holds one element; read it back with
+     * [`SecretWitness::as_single`].
+     */
+    pub fn new_single(value: T) -> Result<Self, MlockError> {
+        Self::new(vec![value])
+    }
+
+    /**
+     * Unlock the backing allocations and hand the contents back to the
+     * caller, without zeroizing them.
+     *
+     * # Remarks
+     * Use this when the secret is being moved into another protected
+     * container (e.g. re-wrapped in a new `SecretWitness` after a
+     * transformation) rather than discarded - it takes `data` out via
+     * [`mem::take`], so the caller's values are moved, not cloned, and
+     * never sit unprotected in an intermediate, un-mlocked allocation.
+     */
+    pub fn into_inner(mut self) -> Vec<T> {
+        for &(address, byte_count) in &self.locked_regions {
+            // Best-effort: if unlocking fails there's nothing left to do,
+            // and the caller now owns `data` regardless.
+            let _ = unlock_memory(address, byte_count);
+        }
+
+        self.locked_regions.clear();
+        self.leaf_regions.clear();
+
+        mem::take(&mut self.data)
+    }
+
+    /// Borrow the secret contents.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Borrow the sole value of a witness constructed via
+    /// [`SecretWitness::new_single`], or `None` if it doesn't hold
+    /// exactly one element - a checked alternative to indexing
+    /// `as_slice()[0]`, which panics instead of reporting a malformed
+    /// witness.
+    pub fn as_single(&self) -> Option<&T> {
+        match self.data.as_slice() {
+            [value] => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Mutably borrow the secret contents.
+    ///
+    /// # Remarks
+    /// `leaf_regions`/`locked_regions` are captured once, at construction
+    /// time, from whatever `T::nested_regions` reports. Writing through
+    /// elements borrowed here is fine (e.g. `matrix[i][j] = value`), but if
+    /// `T` itself owns a further heap allocation (e.g. `T = Vec<i64>`),
+    /// resizing *that* nested allocation through this slice (`push`,
+    /// `resize`, reassigning a whole element) can reallocate it to a new
+    /// address, silently invalidating the region `Drop` later zeroizes and
+    /// `munlock`s. Only mutate leaf scalars in place through this slice;
+    /// to change a nested allocation's size, unwrap with
+    /// [`SecretWitness::into_inner`], resize, and re-wrap with
+    /// [`SecretWitness::new`] instead.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// The number of elements currently stored.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the witness currently holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<T: SecretPayload> Drop for SecretWitness<T> {
+    fn drop(&mut self) {
+        for &(address, byte_count) in &self.leaf_regions {
+            // Safety: `address`/`byte_count` were captured from a live
+            // allocation at lock time and haven't been freed since -
+            // `SecretWitness` owns `data` and nothing else can move or
+            // reallocate the buffers it points into.
+            let bytes = unsafe { std::slice::from_raw_parts_mut(address as *mut u8, byte_count) };
+            bytes.zeroize();
+        }
+
+        for &(address, byte_count) in &self.locked_regions {
+            // Best-effort: if unlocking fails there's nothing left to do,
+            // since the leaf bytes are already zeroed and the pages will
+            // be reclaimed along with the rest of the process image.
+            let _ = unlock_memory(address, byte_count);
+        }
+    }
+}
+
+/**
+ * An `mlock`/`munlock` (or `VirtualLock`/`VirtualUnlock`) syscall failed.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MlockError {
+    /// The `errno` (or `GetLastError` code on Windows) reported by the
+    /// failing syscall.
+    pub errno: i32,
+    /// The starting address of the allocation the lock was attempted on.
+    pub address: usize,
+    /// The number of bytes the lock was attempted over.
+    pub byte_count: usize,
+}
+
+impl fmt::Display for MlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to lock {} byte(s) at address {:#x}: os error {}",
+            self.byte_count, self.address, self.errno
+        )
+    }
+}
+
+impl std::error::Error for MlockError {}
+
+#[cfg(unix)]
+fn lock_memory(address: usize, byte_count: usize) -> Result<(), MlockError> {
+    let ret = unsafe { libc::mlock(address as *const libc::c_void, byte_count) };
+
+    if ret != 0 {
+        return Err(MlockError {
+            errno: io::Error::last_os_error().raw_os_error().unwrap_or(-1),
+            address,
+            byte_count,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn unlock_memory(address: usize, byte_count: usize) -> Result<(), MlockError> {
+    let ret = unsafe { libc::munlock(address as *const libc::c_void, byte_count) };
+
+    if ret != 0 {
+        return Err(MlockError {
+            errno: io::Error::last_os_error().raw_os_error().unwrap_or(-1),
+            address,
+            byte_count,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn lock_memory(address: usize, byte_count: usize) -> Result<(), MlockError> {
+    use winapi::um::memoryapi::VirtualLock;
+
+    let ret = unsafe { VirtualLock(address as *mut winapi::ctypes::c_void, byte_count) };
+
+    if ret == 0 {
+        return Err(MlockError {
+            errno: io::Error::last_os_error().raw_os_error().unwrap_or(-1),
+            address,
+            byte_count,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn unlock_memory(address: usize, byte_count: usize) -> Result<(), MlockError> {
+    use winapi::um::memoryapi::VirtualUnlock;
+
+    let ret = unsafe { VirtualUnlock(address as *mut winapi::ctypes::c_void, byte_count) };
+
+    if ret == 0 {
+        return Err(MlockError {
+            errno: io::Error::last_os_error().raw_os_error().unwrap_or(-1),
+            address,
+            byte_count,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_leaf_region_per_row() {
+        let rows = vec![vec![1i64, 2, 3], vec![4, 5, 6]];
+
+        for row in &rows {
+            let regions = row.nested_regions();
+            assert_eq!(regions.leaf, vec![(row.as_ptr() as usize, 3 * mem::size_of::<i64>())]);
+            assert!(regions.bookkeeping.is_empty());
+        }
+    }
+
+    #[test]
+    fn zeroizes_leaf_regions_before_drop() {
+        // Construct the witness, then zero its leaf regions directly (the
+        // same operation `Drop` performs) while it's still alive, so we
+        // can observe the result without reading freed memory.
+        let mut witness = SecretWitness::new(vec![vec![1i64, 2, 3]]).unwrap();
+
+        for &(address, byte_count) in &witness.leaf_regions.clone() {
+            let bytes = unsafe { std::slice::from_raw_parts_mut(address as *mut u8, byte_count) };
+            bytes.zeroize();
+        }
+
+        assert_eq!(witness.as_mut_slice(), &[vec![0, 0, 0]]);
+    }
+
+    #[test]
+    fn into_inner_moves_data_without_zeroizing() {
+        let witness = SecretWitness::new(vec![vec![1i64, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        let data = witness.into_inner();
+
+        assert_eq!(data, vec![vec![1i64, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn empty_witness_locks_nothing() {
+        let witness: SecretWitness<Vec<i64>> = SecretWitness::new(Vec::new()).unwrap();
+        assert!(witness.is_empty());
+        assert!(witness.locked_regions.is_empty());
+    }
+
+    #[test]
+    fn as_single_returns_the_sole_element() {
+        let witness = SecretWitness::new_single(vec![1i64, 2, 3]).unwrap();
+        assert_eq!(witness.as_single(), Some(&vec![1i64, 2, 3]));
+    }
+
+    #[test]
+    fn as_single_rejects_witnesses_without_exactly_one_element() {
+        let empty: SecretWitness<Vec<i64>> = SecretWitness::new(Vec::new()).unwrap();
+        assert_eq!(empty.as_single(), None);
+
+        let many = SecretWitness::new(vec![vec![1i64], vec![2i64]]).unwrap();
+        assert_eq!(many.as_single(), None);
+    }
+}
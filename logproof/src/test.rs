@@ -2,6 +2,8 @@
  * Types and functions for testing logproof setups. Not meant to be used in
  * production, only for testing.
  */
+use std::mem;
+
 use crypto_bigint::{NonZero, Uint};
 use seal_fhe::{Modulus, PolynomialArray};
 use sunscreen_math::{
@@ -9,7 +11,71 @@ use sunscreen_math::{
     ring::{ArithmeticBackend, Ring, Zq},
 };
 
-use crate::{linear_algebra::Matrix, math::make_poly, rings::ZqRistretto, Bounds};
+use crate::{
+    linear_algebra::Matrix,
+    math::make_poly,
+    rings::ZqRistretto,
+    secret_witness::{MlockError, NestedRegions, SecretPayload, SecretWitness},
+    Bounds,
+};
+
+/**
+ * Finds the leaf coefficient buffers inside a `Matrix<Polynomial<Q>>` -
+ * each polynomial's `coeffs: Vec<Q>` - so [`SecretWitness`] can lock and
+ * zero the actual secret ring elements, not just the matrix's own backing
+ * array of `Polynomial` structs.
+ *
+ * # Remarks
+ * The matrix's backing array of `Polynomial` structs is reported as a
+ * bookkeeping region rather than a leaf: each `Polynomial` embeds its own
+ * `Vec<Q>` pointer/length/capacity, and zeroing those in place would
+ * corrupt the pointers that `Vec<Q>`'s own `Drop` needs to deallocate
+ * correctly.
+ */
+impl<Q> SecretPayload for Matrix<Polynomial<Q>> {
+    fn nested_regions(&self) -> NestedRegions {
+        let mut regions = NestedRegions::default();
+
+        let polys = self.as_slice();
+        if !polys.is_empty() {
+            regions.bookkeeping.push((
+                polys.as_ptr() as usize,
+                polys.capacity() * mem::size_of::<Polynomial<Q>>(),
+            ));
+        }
+
+        // Reported even when empty, so an all-empty matrix's `Polynomial`
+        // structs are never mistaken for leaf bytes and zeroed.
+        for poly in polys {
+            regions.leaf.push((
+                poly.coeffs.as_ptr() as usize,
+                poly.coeffs.capacity() * mem::size_of::<Q>(),
+            ));
+        }
+
+        regions
+    }
+}
+
+/**
+ * Finds the coefficient buffer inside a single `Polynomial<Q>`, the same
+ * way the `Matrix<Polynomial<Q>>` impl above does for a whole matrix of
+ * them - so a [`SecretWitness<Polynomial<Q>>`] can lock and zero the
+ * actual ring-element coefficients.
+ */
+impl<Q> SecretPayload for Polynomial<Q> {
+    fn nested_regions(&self) -> NestedRegions {
+        let mut regions = NestedRegions::default();
+
+        // Reported even when empty, like the `Vec<i64>` impl.
+        regions.leaf.push((
+            self.coeffs.as_ptr() as usize,
+            self.coeffs.capacity() * mem::size_of::<Q>(),
+        ));
+
+        regions
+    }
+}
 
 /**
  * All information for a problem of the form `AS = T` in `Z_q[X]/f`. Useful for
@@ -20,12 +86,15 @@ use crate::{linear_algebra::Matrix, math::make_poly, rings::ZqRistretto, Bounds}
 pub struct LatticeProblem<Q>
 where
     Q: Ring,
+    Matrix<Polynomial<Q>>: SecretPayload,
 {
     /// Public A
     pub a: Matrix<Polynomial<Q>>,
 
-    /// Private message and encryption components S
-    pub s: Matrix<Polynomial<Q>>,
+    /// Private message and encryption components S. Locked in memory and
+    /// zeroized on drop, since this is the witness a full-knowledge or
+    /// zero-knowledge proof must never leak.
+    pub s: SecretWitness<Matrix<Polynomial<Q>>>,
 
     /// Result of A * S
     pub t: Matrix<Polynomial<Q>>,
@@ -58,11 +127,16 @@ where
  * moduli in its associated modulus set into regular integers. The main
  * advantage here over using a polynomial in its normal field is that the
  * polynomial can be moved to a new field without modulus switching.
+ *
+ * # Remarks
+ * The returned buffer is locked in memory and zeroized on drop, since these
+ * small coefficients are the same secret material as the original
+ * `PolynomialArray`, just laid out differently.
  */
 pub fn convert_to_smallint(
     coeff_modulus: &[Modulus],
     poly_array: PolynomialArray,
-) -> Vec<Vec<i64>> {
+) -> Result<SecretWitness<Vec<i64>>, MlockError> {
     let first_coefficient = coeff_modulus[0].value();
 
     let rns = poly_array.as_rns_u64s().unwrap();
@@ -89,7 +163,7 @@ pub fn convert_to_smallint(
         }
     }
 
-    result
+    SecretWitness::new(result)
 }
 
 /**
@@ -100,28 +174,46 @@ pub fn convert_to_smallint(
 pub fn convert_to_small_coeffs(
     coeff_modulus: &[Modulus],
     poly_array: PolynomialArray,
-) -> Vec<Vec<i64>> {
-    convert_to_smallint(coeff_modulus, poly_array)
+) -> Result<SecretWitness<Vec<i64>>, MlockError> {
+    let stripped = convert_to_smallint(coeff_modulus, poly_array)?
+        .into_inner()
         .into_iter()
         .map(|v| strip_trailing_value(v, 0))
-        .collect()
+        .collect::<Vec<_>>();
+
+    SecretWitness::new(stripped)
 }
 
 /**
  * Convert a `PolynomialArray` to a vector of `DensePolynomial`, where all the
  * coefficients are small (less than any of the constituent coefficient moduli).
+ *
+ * # Remarks
+ * The returned polynomials are locked in memory and zeroized on drop,
+ * same as [`convert_to_small_coeffs`]'s output - `make_poly` only changes
+ * how the secret coefficients are laid out, not their sensitivity. The
+ * small-int witness is only borrowed, not moved out via `into_inner`:
+ * `make_poly` builds a brand-new `Polynomial<Q>` rather than reusing the
+ * `Vec<i64>` buffers, so the original witness must stay wrapped and get
+ * dropped normally here, locked and zeroized like any other, instead of
+ * being unlocked early and left to leak as a bare `Vec`.
  */
 pub fn convert_to_polynomial_by_small_coeffs<Q>(
     coeff_modulus: &[Modulus],
     poly_array: PolynomialArray,
-) -> Vec<Polynomial<Q>>
+) -> Result<SecretWitness<Polynomial<Q>>, MlockError>
 where
     Q: Ring + From<u64>,
 {
-    convert_to_small_coeffs(coeff_modulus, poly_array)
-        .into_iter()
-        .map(|v| make_poly(&v))
-        .collect::<Vec<Polynomial<Q>>>()
+    let small_coeffs = convert_to_small_coeffs(coeff_modulus, poly_array)?;
+
+    let polynomials = small_coeffs
+        .as_slice()
+        .iter()
+        .map(|v| make_poly(v))
+        .collect::<Vec<Polynomial<Q>>>();
+
+    SecretWitness::new(polynomials)
 }
 
 /**